@@ -1,4 +1,9 @@
+#![allow(unexpected_cfgs)]
+
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
 declare_id!("GQxHpCW7Uv7DS2LxLS9sh7Tkstug27Ho14JiZTFJ3n2H");
 
@@ -8,9 +13,360 @@ pub mod solana_deploy_contract_fordefi {
 
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         msg!("Greetings from Fordefi! {:?}", ctx.program_id);
+
+        let config = &mut ctx.accounts.config;
+        config.authority = ctx.accounts.authority.key();
+        config.bump = ctx.bumps.config;
+        config.pending_authority = None;
+        config.paused = false;
+        config.price_feed_id = [0u8; 32];
+        config.last_price = 0;
+        config.last_price_update_slot = 0;
+
+        emit!(Initialized {
+            authority: config.authority,
+        });
+
+        Ok(())
+    }
+
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        config.pending_authority = Some(new_authority);
+        Ok(())
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        let config = &mut ctx.accounts.config;
+        let pending = config.pending_authority.ok_or(ErrorCode::NoPendingAuthority)?;
+        require_keys_eq!(pending, ctx.accounts.pending_authority.key(), ErrorCode::Unauthorized);
+
+        let old = config.authority;
+        config.authority = pending;
+        config.pending_authority = None;
+
+        emit!(AuthorityTransferred {
+            old,
+            new: pending,
+        });
+
         Ok(())
     }
+
+    pub fn set_paused(ctx: Context<SetPaused>, paused: bool) -> Result<()> {
+        ctx.accounts.config.paused = paused;
+
+        emit!(Paused { paused });
+
+        Ok(())
+    }
+
+    pub fn set_price_feed_id(ctx: Context<SetPriceFeedId>, feed_id: [u8; 32]) -> Result<()> {
+        ctx.accounts.config.price_feed_id = feed_id;
+        Ok(())
+    }
+
+    pub fn read_price(ctx: Context<ReadPrice>, max_age: u64) -> Result<i128> {
+        let config = &mut ctx.accounts.config;
+        let price_update = &ctx.accounts.price_update;
+
+        let feed_id = price_update.price_message.feed_id;
+        require!(feed_id == config.price_feed_id, ErrorCode::PriceFeedMismatch);
+
+        let publish_time = price_update.price_message.publish_time;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now.saturating_sub(publish_time) <= max_age as i64,
+            ErrorCode::StalePrice
+        );
+
+        let price = price_update.price_message.price;
+        let exponent = price_update.price_message.exponent;
+        let scaled_price = scale_price(price, exponent)?;
+
+        config.last_price = scaled_price;
+        config.last_price_update_slot = Clock::get()?.slot;
+
+        Ok(scaled_price)
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.user_token_account.to_account_info(),
+            to: ctx.accounts.vault_token_account.to_account_info(),
+            authority: ctx.accounts.depositor.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.key(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        require!(!ctx.accounts.config.paused, ErrorCode::ProgramPaused);
+
+        let bump = ctx.accounts.config.bump;
+        let seeds: &[&[u8]] = &[b"config", &[bump]];
+        let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault_token_account.to_account_info(),
+            to: ctx.accounts.recipient_token_account.to_account_info(),
+            authority: ctx.accounts.config.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.key(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)
+    }
+}
+
+/// Canonical number of decimals `last_price` is scaled to, regardless of the
+/// feed's own exponent. Scaling up (instead of truncating down to whole
+/// units) keeps sub-unit precision for feeds like SOL/USD (`exponent` ~ -8).
+const CANONICAL_EXPONENT: i32 = -6;
+
+fn scale_price(price: i64, exponent: i32) -> Result<i128> {
+    let price = price as i128;
+    let shift = exponent
+        .checked_sub(CANONICAL_EXPONENT)
+        .ok_or(ErrorCode::PriceScalingOverflow)?;
+    if shift >= 0 {
+        let factor = 10i128
+            .checked_pow(shift as u32)
+            .ok_or(ErrorCode::PriceScalingOverflow)?;
+        Ok(price.checked_mul(factor).ok_or(ErrorCode::PriceScalingOverflow)?)
+    } else {
+        let factor = 10i128
+            .checked_pow(shift.checked_neg().ok_or(ErrorCode::PriceScalingOverflow)? as u32)
+            .ok_or(ErrorCode::PriceScalingOverflow)?;
+        Ok(price / factor)
+    }
+}
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + 32 + 1 + 33 + 1 + 32 + 16 + 8,
+        seeds = [b"config"],
+        bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub pending_authority: Signer<'info>,
 }
 
 #[derive(Accounts)]
-pub struct Initialize {}
+pub struct SetPaused<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPriceFeedId<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReadPrice<'info> {
+    #[account(
+        mut,
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub price_update: Account<'info, PriceUpdateV2>,
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_token_account.mint == mint.key() @ ErrorCode::MintMismatch,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = depositor,
+        associated_token::mint = mint,
+        associated_token::authority = config,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        has_one = authority @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = config,
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = recipient_token_account.mint == mint.key() @ ErrorCode::MintMismatch,
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[account]
+pub struct Config {
+    pub authority: Pubkey,
+    pub bump: u8,
+    pub pending_authority: Option<Pubkey>,
+    pub paused: bool,
+    pub price_feed_id: [u8; 32],
+    pub last_price: i128,
+    pub last_price_update_slot: u64,
+}
+
+#[event]
+pub struct Initialized {
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub old: Pubkey,
+    pub new: Pubkey,
+}
+
+#[event]
+pub struct Paused {
+    pub paused: bool,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("The signer is not authorized to perform this action")]
+    Unauthorized,
+    #[msg("There is no pending authority to accept")]
+    NoPendingAuthority,
+    #[msg("The program is paused")]
+    ProgramPaused,
+    #[msg("The price update does not match the configured feed id")]
+    PriceFeedMismatch,
+    #[msg("The price update is older than the allowed max age")]
+    StalePrice,
+    #[msg("Scaling the price by its exponent overflowed")]
+    PriceScalingOverflow,
+    #[msg("The token account's mint does not match the vault mint")]
+    MintMismatch,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale_price_matches_canonical_exponent_exactly() {
+        assert_eq!(scale_price(140_123_456, CANONICAL_EXPONENT).unwrap(), 140_123_456);
+    }
+
+    #[test]
+    fn scale_price_divides_for_exponents_finer_than_canonical() {
+        // SOL/USD-style feed: $140.12345678 at exponent -8, two decimals
+        // finer than the -6 canonical exponent.
+        assert_eq!(scale_price(14_012_345_678, -8).unwrap(), 140_123_456);
+    }
+
+    #[test]
+    fn scale_price_multiplies_for_exponents_coarser_than_canonical() {
+        assert_eq!(scale_price(14_012, -3).unwrap(), 14_012_000);
+    }
+
+    #[test]
+    fn scale_price_multiplies_for_positive_exponents() {
+        assert_eq!(scale_price(5, 2).unwrap(), 500_000_000);
+    }
+
+    #[test]
+    fn scale_price_preserves_sub_unit_precision_instead_of_truncating_to_zero() {
+        // At exponent -8 this is $0.00012345 — whole-unit truncation
+        // (price / 10^8) would give 0; the canonical scaling keeps 123.
+        assert_eq!(scale_price(12_345, -8).unwrap(), 123);
+    }
+
+    #[test]
+    fn scale_price_rejects_overflowing_shift() {
+        assert!(scale_price(i64::MAX, i32::MAX).is_err());
+    }
+}
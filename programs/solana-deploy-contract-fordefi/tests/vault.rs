@@ -0,0 +1,264 @@
+//! Integration tests for the SPL token vault (`deposit`/`withdraw`), run
+//! against an in-process validator via litesvm. These exercise the
+//! custody-relevant paths a unit test can't: the `paused` guard on both
+//! sides of the vault, and the mint-mismatch constraint.
+//!
+//! Requires the program to be built with `cargo build-sbf` first so
+//! `target/deploy/solana_deploy_contract_fordefi.so` exists.
+
+use anchor_lang::{InstructionData, ToAccountMetas};
+use litesvm::LiteSVM;
+use solana_deploy_contract_fordefi::{accounts, instruction, ID};
+use solana_sdk::{
+    instruction::Instruction,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use solana_system_interface::{instruction as system_instruction, program as system_program};
+use spl_associated_token_account::get_associated_token_address;
+
+const PROGRAM_SO: &str = "../../target/deploy/solana_deploy_contract_fordefi.so";
+
+fn config_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &ID).0
+}
+
+struct Env {
+    svm: LiteSVM,
+    authority: Keypair,
+    depositor: Keypair,
+    mint: Pubkey,
+}
+
+fn setup() -> Env {
+    let mut svm = LiteSVM::new();
+    svm.add_program_from_file(ID, PROGRAM_SO)
+        .expect("build the program with `cargo build-sbf` first");
+
+    let authority = Keypair::new();
+    let depositor = Keypair::new();
+    for payer in [&authority, &depositor] {
+        svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+    }
+
+    let mint = Keypair::new();
+    create_mint(&mut svm, &authority, &mint);
+
+    send(
+        &mut svm,
+        &authority,
+        &[Instruction {
+            program_id: ID,
+            accounts: accounts::Initialize {
+                config: config_pda(),
+                authority: authority.pubkey(),
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::Initialize {}.data(),
+        }],
+    )
+    .unwrap();
+
+    Env {
+        svm,
+        authority,
+        depositor,
+        mint: mint.pubkey(),
+    }
+}
+
+fn create_mint(svm: &mut LiteSVM, payer: &Keypair, mint: &Keypair) {
+    let rent = svm.minimum_balance_for_rent_exemption(spl_token::state::Mint::LEN);
+    let create_account_ix = system_instruction::create_account(
+        &payer.pubkey(),
+        &mint.pubkey(),
+        rent,
+        spl_token::state::Mint::LEN as u64,
+        &spl_token::ID,
+    );
+    let init_mint_ix = spl_token::instruction::initialize_mint2(
+        &spl_token::ID,
+        &mint.pubkey(),
+        &payer.pubkey(),
+        None,
+        6,
+    )
+    .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_account_ix, init_mint_ix],
+        Some(&payer.pubkey()),
+        &[payer, mint],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).unwrap();
+}
+
+fn create_and_fund_token_account(svm: &mut LiteSVM, payer: &Keypair, owner: &Pubkey, mint: &Pubkey, amount: u64) -> Pubkey {
+    let ata = get_associated_token_address(owner, mint);
+    let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+        &payer.pubkey(),
+        owner,
+        mint,
+        &spl_token::ID,
+    );
+    let mint_to_ix =
+        spl_token::instruction::mint_to(&spl_token::ID, mint, &ata, &payer.pubkey(), &[], amount)
+            .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[create_ata_ix, mint_to_ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx).unwrap();
+    ata
+}
+
+#[allow(clippy::result_large_err)]
+fn send(svm: &mut LiteSVM, payer: &Keypair, ixs: &[Instruction]) -> Result<(), litesvm::types::FailedTransactionMetadata> {
+    let tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &[payer], svm.latest_blockhash());
+    svm.send_transaction(tx).map(|_| ())
+}
+
+fn set_paused(svm: &mut LiteSVM, authority: &Keypair, paused: bool) {
+    send(
+        svm,
+        authority,
+        &[Instruction {
+            program_id: ID,
+            accounts: accounts::SetPaused {
+                config: config_pda(),
+                authority: authority.pubkey(),
+            }
+            .to_account_metas(None),
+            data: instruction::SetPaused { paused }.data(),
+        }],
+    )
+    .unwrap();
+}
+
+#[test]
+fn deposit_rejected_while_paused() {
+    let mut env = setup();
+    let depositor_ata =
+        create_and_fund_token_account(&mut env.svm, &env.authority, &env.depositor.pubkey(), &env.mint, 1_000);
+    let vault_ata = get_associated_token_address(&config_pda(), &env.mint);
+
+    set_paused(&mut env.svm, &env.authority, true);
+
+    let result = send(
+        &mut env.svm,
+        &env.depositor,
+        &[Instruction {
+            program_id: ID,
+            accounts: accounts::Deposit {
+                config: config_pda(),
+                mint: env.mint,
+                user_token_account: depositor_ata,
+                vault_token_account: vault_ata,
+                depositor: env.depositor.pubkey(),
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::Deposit { amount: 100 }.data(),
+        }],
+    );
+
+    assert!(result.is_err(), "deposit must be rejected while the program is paused");
+}
+
+#[test]
+fn withdraw_rejected_while_paused() {
+    let mut env = setup();
+    let depositor_ata =
+        create_and_fund_token_account(&mut env.svm, &env.authority, &env.depositor.pubkey(), &env.mint, 1_000);
+    let recipient_ata =
+        create_and_fund_token_account(&mut env.svm, &env.authority, &env.authority.pubkey(), &env.mint, 0);
+    let vault_ata = get_associated_token_address(&config_pda(), &env.mint);
+
+    send(
+        &mut env.svm,
+        &env.depositor,
+        &[Instruction {
+            program_id: ID,
+            accounts: accounts::Deposit {
+                config: config_pda(),
+                mint: env.mint,
+                user_token_account: depositor_ata,
+                vault_token_account: vault_ata,
+                depositor: env.depositor.pubkey(),
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::Deposit { amount: 500 }.data(),
+        }],
+    )
+    .unwrap();
+
+    set_paused(&mut env.svm, &env.authority, true);
+
+    let result = send(
+        &mut env.svm,
+        &env.authority,
+        &[Instruction {
+            program_id: ID,
+            accounts: accounts::Withdraw {
+                config: config_pda(),
+                mint: env.mint,
+                vault_token_account: vault_ata,
+                recipient_token_account: recipient_ata,
+                authority: env.authority.pubkey(),
+                token_program: spl_token::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::Withdraw { amount: 500 }.data(),
+        }],
+    );
+
+    assert!(result.is_err(), "withdraw must be rejected while the program is paused");
+}
+
+#[test]
+fn deposit_rejected_on_mint_mismatch() {
+    let mut env = setup();
+    let depositor_ata =
+        create_and_fund_token_account(&mut env.svm, &env.authority, &env.depositor.pubkey(), &env.mint, 1_000);
+    let vault_ata = get_associated_token_address(&config_pda(), &env.mint);
+
+    let other_mint = Keypair::new();
+    create_mint(&mut env.svm, &env.authority, &other_mint);
+
+    let result = send(
+        &mut env.svm,
+        &env.depositor,
+        &[Instruction {
+            program_id: ID,
+            accounts: accounts::Deposit {
+                config: config_pda(),
+                mint: other_mint.pubkey(),
+                user_token_account: depositor_ata,
+                vault_token_account: vault_ata,
+                depositor: env.depositor.pubkey(),
+                token_program: spl_token::ID,
+                associated_token_program: spl_associated_token_account::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: instruction::Deposit { amount: 100 }.data(),
+        }],
+    );
+
+    assert!(
+        result.is_err(),
+        "deposit must be rejected when the user token account's mint doesn't match the declared mint"
+    );
+}